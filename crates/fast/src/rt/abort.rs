@@ -0,0 +1,209 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering::*},
+    },
+    task::{Context, Poll, Waker},
+};
+
+use pin_project::pin_project;
+
+use super::{Act, BoxSendFut, Key, Kind, Task};
+
+static NEXT_KEY: AtomicUsize = AtomicUsize::new(0);
+
+// Standalone key counter for tasks built outside the (not-yet-present-here)
+// worker scheduler, e.g. by `spawn_abortable` and the supervision tree.
+pub(crate) fn next_key<K: Kind>() -> Key<K> {
+    Key(NEXT_KEY.fetch_add(1, Relaxed), PhantomData)
+}
+
+/// Returned by an [`Abortable`] future when it was cancelled through its
+/// [`AbortHandle`] before completing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aborted;
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "future was aborted")
+    }
+}
+
+impl std::error::Error for Aborted {}
+
+struct Inner {
+    aborted: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// Handle for cancelling a running [`Abortable`] future.
+#[derive(Clone)]
+pub struct AbortHandle {
+    inner: Arc<Inner>,
+}
+
+/// The other half of an [`AbortHandle`]; hand this to [`Abortable::new`].
+pub struct AbortRegistration {
+    inner: Arc<Inner>,
+}
+
+impl AbortHandle {
+    /// Build a fresh handle/registration pair for one [`Abortable`] future.
+    pub fn new_pair() -> (AbortHandle, AbortRegistration) {
+        let inner = Arc::new(Inner {
+            aborted: AtomicBool::new(false),
+            waker: Mutex::new(None),
+        });
+
+        (
+            AbortHandle {
+                inner: inner.clone(),
+            },
+            AbortRegistration { inner },
+        )
+    }
+
+    /// Cancel the associated future, waking it so it promptly observes the
+    /// cancellation and is rescheduled through `K::schedule`.
+    pub fn abort(&self) {
+        self.inner.aborted.store(true, Release);
+        if let Some(waker) = self.inner.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.inner.aborted.load(Acquire)
+    }
+}
+
+/// Wraps a future so it can be cancelled from the outside via an
+/// [`AbortHandle`].
+#[pin_project]
+pub struct Abortable<F> {
+    #[pin]
+    future: F,
+    registration: AbortRegistration,
+}
+
+impl<F: Future> Abortable<F> {
+    pub fn new(future: F, registration: AbortRegistration) -> Self {
+        Self {
+            future,
+            registration,
+        }
+    }
+}
+
+impl<F: Future> Future for Abortable<F> {
+    type Output = Result<F::Output, Aborted>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.registration.inner.aborted.load(Acquire) {
+            return Poll::Ready(Err(Aborted));
+        }
+
+        *this.registration.inner.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        // `abort` may have raced in between our first check and registering
+        // the waker above; re-check so we don't poll a cancelled future.
+        if this.registration.inner.aborted.load(Acquire) {
+            return Poll::Ready(Err(Aborted));
+        }
+
+        this.future.poll(cx).map(Ok)
+    }
+}
+
+/// Build a `Task<K>` that runs `future` until it completes or is cancelled
+/// through the returned `AbortHandle`.
+pub fn spawn_abortable<K>(future: impl Future<Output = ()> + Send + 'static) -> (Task<K>, AbortHandle)
+where
+    K: Kind + BoxSendFut,
+{
+    let (handle, registration) = AbortHandle::new_pair();
+    let abortable = Abortable::new(future, registration);
+
+    let act = Act::Fut(K::boxed(async move {
+        let _ = abortable.await;
+    }));
+
+    let key = next_key::<K>();
+
+    (Task { key, act: Some(act) }, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::AtomicUsize, task::Wake};
+
+    use super::*;
+
+    struct Pending;
+
+    impl Future for Pending {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            Poll::Pending
+        }
+    }
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.fetch_add(1, Relaxed);
+        }
+    }
+
+    #[test]
+    fn abort_wakes_the_registered_waker_and_resolves_to_aborted() {
+        let (handle, registration) = AbortHandle::new_pair();
+        let mut abortable = Abortable::new(Pending, registration);
+
+        let wakes = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(wakes.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut abortable).poll(&mut cx).is_pending());
+        assert!(!handle.is_aborted());
+
+        handle.abort();
+        assert!(handle.is_aborted());
+        assert_eq!(
+            wakes.0.load(Relaxed),
+            1,
+            "abort() must wake the future's registered waker"
+        );
+
+        assert_eq!(
+            Pin::new(&mut abortable).poll(&mut cx),
+            Poll::Ready(Err(Aborted))
+        );
+    }
+
+    #[test]
+    fn aborting_before_the_first_poll_is_observed_immediately() {
+        let (handle, registration) = AbortHandle::new_pair();
+        let mut abortable = Abortable::new(Pending, registration);
+        handle.abort();
+
+        let waker = Waker::from(Arc::new(CountingWaker(AtomicUsize::new(0))));
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(
+            Pin::new(&mut abortable).poll(&mut cx),
+            Poll::Ready(Err(Aborted))
+        );
+    }
+}
@@ -4,14 +4,15 @@ use std::{
     mem::{self, ManuallyDrop},
     pin::{Pin, pin},
     sync::{
-        Arc, OnceLock,
-        atomic::{AtomicPtr, AtomicU8, Ordering::*},
+        Arc, Condvar, Mutex, OnceLock,
+        atomic::{AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering::*},
     },
     task::{
         Context,
         Poll::{self, *},
-        RawWaker, RawWakerVTable, Wake, Waker,
+        Wake, Waker,
     },
+    time::{Duration, Instant},
 };
 
 use pin_project::pin_project;
@@ -19,8 +20,15 @@ use worker::{current_worker, select_worker};
 
 use crate::{collections::queue::Queue, sync::split::Split};
 
+pub mod abort;
+pub mod shared;
+pub mod supervisor;
 pub mod worker;
 
+pub use abort::{Abortable, AbortHandle, AbortRegistration, Aborted, spawn_abortable};
+pub use shared::{FutureExt, Shared};
+pub use supervisor::{RestartPolicy, Supervisor, TaskNode, TaskState};
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Local;
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -36,6 +44,18 @@ pub trait Kind: Copy {
     async fn schedule(task: Task<Self>);
 }
 
+/// Build `Self::Fut` from a boxed, `Send` future.
+///
+/// Split out of [`Kind`] itself rather than folded into it: `Local`'s whole
+/// reason for existing is to host futures that aren't `Send`, and a `Send`
+/// bound on a `Kind` method would rule those out even though nothing about
+/// `Local::Fut` requires it. Callers that only ever have a `Send` future to
+/// box up (`abort::spawn_abortable` and `supervisor::Supervisor`) can still
+/// ask for this bound explicitly via `K: Kind + BoxSendFut`.
+pub trait BoxSendFut: Kind {
+    fn boxed(fut: impl Future<Output = ()> + Send + 'static) -> Self::Fut;
+}
+
 impl Kind for Local {
     type Call = Box<dyn FnOnce()>;
     type Fut = Pin<Box<dyn Future<Output = ()>>>;
@@ -55,6 +75,12 @@ impl Kind for Local {
     }
 }
 
+impl BoxSendFut for Local {
+    fn boxed(fut: impl Future<Output = ()> + Send + 'static) -> Self::Fut {
+        Box::pin(fut)
+    }
+}
+
 impl Kind for Remote {
     type Call = Box<dyn FnOnce() + Send>;
     type Fut = Pin<Box<dyn Future<Output = ()> + Send>>;
@@ -74,6 +100,12 @@ impl Kind for Remote {
     }
 }
 
+impl BoxSendFut for Remote {
+    fn boxed(fut: impl Future<Output = ()> + Send + 'static) -> Self::Fut {
+        Box::pin(fut)
+    }
+}
+
 struct NotifyWaker<K: Kind>(Arc<Notify<K>>);
 
 impl<K: Kind> Wake for NotifyWaker<K> {
@@ -230,42 +262,157 @@ pub fn poll(
     pin!(fut).poll(cx)
 }
 
-const BLOCK_ON_VTABLE: RawWakerVTable = RawWakerVTable::new(
-    |_| RawWaker::new(std::ptr::null(), &BLOCK_ON_VTABLE),
-    |_| {},
-    |_| {},
-    |_| {},
-);
-pub const BLOCK_ON: &Waker =
-    unsafe { &Waker::from_raw(RawWaker::new(std::ptr::null(), &BLOCK_ON_VTABLE)) };
+struct Parker {
+    state: AtomicUsize,
+    lock: Mutex<()>,
+    cvar: Condvar,
+}
+
+impl Parker {
+    const EMPTY: usize = 0;
+    const PARKED: usize = 1;
+    const NOTIFIED: usize = 2;
+
+    fn new() -> Self {
+        Self {
+            state: AtomicUsize::new(Self::EMPTY),
+            lock: Mutex::new(()),
+            cvar: Condvar::new(),
+        }
+    }
+
+    // Wake up a parked thread, or leave a notification behind for the next
+    // call to `park`/`park_timeout` if nobody is parked yet.
+    fn unpark(&self) {
+        if self.state.swap(Self::NOTIFIED, AcqRel) == Self::PARKED {
+            let _guard = self.lock.lock().unwrap();
+            self.cvar.notify_one();
+        }
+    }
+
+    fn park(&self) {
+        // Fast path: a notification already arrived, consume it and return.
+        if self.state.compare_exchange(Self::NOTIFIED, Self::EMPTY, AcqRel, Acquire).is_ok() {
+            return;
+        }
+
+        let mut guard = self.lock.lock().unwrap();
+
+        // If a notification raced us in before we took the lock, pick it up
+        // instead of clobbering it with PARKED.
+        if self.state.compare_exchange(Self::EMPTY, Self::PARKED, AcqRel, Acquire).is_err() {
+            self.state.store(Self::EMPTY, Release);
+            return;
+        }
+
+        loop {
+            if self.state.load(Acquire) == Self::NOTIFIED {
+                self.state.store(Self::EMPTY, Release);
+                return;
+            }
+            guard = self.cvar.wait(guard).unwrap();
+        }
+    }
+
+    fn park_timeout(&self, timeout: Duration) -> bool {
+        if self.state.compare_exchange(Self::NOTIFIED, Self::EMPTY, AcqRel, Acquire).is_ok() {
+            return true;
+        }
+
+        let mut guard = self.lock.lock().unwrap();
+
+        if self.state.compare_exchange(Self::EMPTY, Self::PARKED, AcqRel, Acquire).is_err() {
+            self.state.store(Self::EMPTY, Release);
+            return true;
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            if self.state.load(Acquire) == Self::NOTIFIED {
+                self.state.store(Self::EMPTY, Release);
+                return true;
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                // Give up: leave the state as PARKED->EMPTY so a late notify
+                // doesn't get lost on whoever parks next.
+                self.state.compare_exchange(Self::PARKED, Self::EMPTY, AcqRel, Acquire).ok();
+                return false;
+            }
+
+            let (next_guard, _) = self.cvar.wait_timeout(guard, remaining).unwrap();
+            guard = next_guard;
+        }
+    }
+}
+
+struct BlockOnWaker {
+    parker: Arc<Parker>,
+}
+
+impl Wake for BlockOnWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.parker.unpark();
+    }
+}
 
 pub struct Block<F> {
     future: Pin<Box<F>>,
-    context: Context<'static>,
+    parker: Arc<Parker>,
+    waker: Waker,
 }
 
 impl<F: Future> Block<F> {
     pub fn new(future: F) -> Self {
+        let parker = Arc::new(Parker::new());
+        let waker = Waker::from(Arc::new(BlockOnWaker {
+            parker: parker.clone(),
+        }));
+
         Self {
             future: Box::pin(future),
-            context: Context::from_waker(BLOCK_ON),
+            parker,
+            waker,
         }
     }
-}
 
-impl<F: Future> Block<F> {
     pub fn poll(&mut self) -> Poll<F::Output> {
-        self.future.as_mut().poll(&mut self.context)
+        let mut cx = Context::from_waker(&self.waker);
+        self.future.as_mut().poll(&mut cx)
     }
 }
 
-pub fn block_on<F: Future>(mut future: F) -> F::Output {
-    let mut fut = Block::new(future);
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut block = Block::new(future);
     loop {
-        let Poll::Ready(x) = fut.poll() else {
-            continue;
-        };
-        break x;
+        match block.poll() {
+            Poll::Ready(output) => break output,
+            Poll::Pending => block.parker.park(),
+        }
+    }
+}
+
+/// Like `block_on`, but gives up and returns `None` once `timeout` elapses
+/// without the future becoming ready.
+pub fn block_on_timeout<F: Future>(future: F, timeout: Duration) -> Option<F::Output> {
+    let mut block = Block::new(future);
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match block.poll() {
+            Poll::Ready(output) => break Some(output),
+            Poll::Pending => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() || !block.parker.park_timeout(remaining) {
+                    break None;
+                }
+            }
+        }
     }
 }
 
@@ -355,11 +502,275 @@ pub trait JoinExt: Future + Sized {
 
 impl<F: Future> JoinExt for F {}
 
-pub struct Select<T, U> {
-    first: Pin<Box<T>>,
-    second: Pin<Box<U>>,
-    polled_first: bool,
-    polled_second: bool,
+const JOIN_ALL_MAX_CONSECUTIVE_POLLS: usize = 16;
+
+enum JoinAllState<F: Future> {
+    Pending(Pin<Box<F>>),
+    Done(Box<F::Output>),
+}
+
+struct JoinAllChild<F: Future> {
+    woken: Arc<AtomicBool>,
+    state: JoinAllState<F>,
+}
+
+struct JoinAllWaker {
+    woken: Arc<AtomicBool>,
+    parent: Waker,
+}
+
+impl Wake for JoinAllWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Release);
+        self.parent.wake_by_ref();
+    }
+}
+
+/// Drives an arbitrary collection of futures to completion, resolving to
+/// their outputs in input order.
+///
+/// Unlike `Join`, each child tracks its own "woken" flag so a wakeup only
+/// re-polls the children that asked for it, instead of re-polling every
+/// child on every wakeup.
+pub struct JoinAll<F: Future> {
+    children: Vec<JoinAllChild<F>>,
+    total_complete: usize,
+    next_poll_index: usize,
+}
+
+impl<F: Future> JoinAll<F> {
+    pub fn new(iter: impl IntoIterator<Item = F>) -> Self {
+        let children = iter
+            .into_iter()
+            .map(|fut| JoinAllChild {
+                woken: Arc::new(AtomicBool::new(true)),
+                state: JoinAllState::Pending(Box::pin(fut)),
+            })
+            .collect();
+
+        Self {
+            children,
+            total_complete: 0,
+            next_poll_index: 0,
+        }
+    }
+}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let len = self.children.len();
+        if len == 0 {
+            return Poll::Ready(Vec::new());
+        }
+
+        let mut advanced = 0;
+        let mut scanned = 0;
+        let mut index = self.next_poll_index % len;
+
+        while self.total_complete < len
+            && advanced < JOIN_ALL_MAX_CONSECUTIVE_POLLS
+            && scanned < len
+        {
+            let child = &mut self.children[index];
+
+            if matches!(child.state, JoinAllState::Pending(_)) && child.woken.swap(false, AcqRel) {
+                let waker = Waker::from(Arc::new(JoinAllWaker {
+                    woken: child.woken.clone(),
+                    parent: cx.waker().clone(),
+                }));
+                let mut child_cx = Context::from_waker(&waker);
+
+                if let JoinAllState::Pending(fut) = &mut child.state {
+                    if let Poll::Ready(output) = fut.as_mut().poll(&mut child_cx) {
+                        child.state = JoinAllState::Done(Box::new(output));
+                        self.total_complete += 1;
+                    }
+                }
+
+                advanced += 1;
+            }
+
+            index = (index + 1) % len;
+            scanned += 1;
+        }
+
+        self.next_poll_index = index;
+
+        if self.total_complete == len {
+            let children = mem::take(&mut self.children);
+            return Poll::Ready(
+                children
+                    .into_iter()
+                    .map(|child| match child.state {
+                        JoinAllState::Done(output) => *output,
+                        JoinAllState::Pending(_) => unreachable!("all children are done"),
+                    })
+                    .collect(),
+            );
+        }
+
+        // If we stopped early because we hit the per-poll cap rather than
+        // because we ran out of woken children to advance, some children may
+        // still be sitting on `woken = true` unpolled (their inner future
+        // never got a chance to register a waker). Re-arm the parent waker
+        // ourselves so we get polled again instead of relying on a wakeup
+        // that will never come.
+        if advanced == JOIN_ALL_MAX_CONSECUTIVE_POLLS {
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+// Utility function to join a collection of futures
+pub fn join_all<F: Future>(iter: impl IntoIterator<Item = F>) -> JoinAll<F> {
+    JoinAll::new(iter)
+}
+
+// Extension trait for more ergonomic usage over collections of futures
+pub trait JoinAllExt: IntoIterator + Sized
+where
+    Self::Item: Future,
+{
+    fn join_all(self) -> JoinAll<Self::Item> {
+        JoinAll::new(self)
+    }
+}
+
+impl<I: IntoIterator> JoinAllExt for I where I::Item: Future {}
+
+#[cfg(test)]
+mod join_all_tests {
+    use super::*;
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.fetch_add(1, SeqCst);
+        }
+    }
+
+    struct Immediate(usize);
+
+    impl Future for Immediate {
+        type Output = usize;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<usize> {
+            Poll::Ready(self.0)
+        }
+    }
+
+    struct Gated {
+        value: usize,
+        gate: Arc<AtomicBool>,
+    }
+
+    impl Future for Gated {
+        type Output = usize;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<usize> {
+            if self.gate.load(SeqCst) {
+                Poll::Ready(self.value)
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    // Regression test for a hang where, with more children than
+    // `JOIN_ALL_MAX_CONSECUTIVE_POLLS`, hitting the cap before scanning every
+    // child left the unscanned children's wakeups with nowhere to go.
+    #[test]
+    fn rearms_parent_waker_when_the_poll_cap_is_hit() {
+        let gate = Arc::new(AtomicBool::new(false));
+
+        let mut futures: Vec<Pin<Box<dyn Future<Output = usize>>>> = (0..JOIN_ALL_MAX_CONSECUTIVE_POLLS)
+            .map(|i| Box::pin(Immediate(i)) as Pin<Box<dyn Future<Output = usize>>>)
+            .collect();
+        futures.push(Box::pin(Gated {
+            value: JOIN_ALL_MAX_CONSECUTIVE_POLLS,
+            gate: gate.clone(),
+        }));
+
+        let mut joined = JoinAll::new(futures);
+        let wakes = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(wakes.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let first = Pin::new(&mut joined).poll(&mut cx);
+        assert!(first.is_pending(), "the gated child hasn't resolved yet");
+        assert_eq!(
+            wakes.0.load(SeqCst),
+            1,
+            "hitting the cap before scanning every child must re-arm the parent waker"
+        );
+
+        gate.store(true, SeqCst);
+        match Pin::new(&mut joined).poll(&mut cx) {
+            Poll::Ready(results) => assert_eq!(results.len(), JOIN_ALL_MAX_CONSECUTIVE_POLLS + 1),
+            Poll::Pending => panic!("all children should be done by the second poll"),
+        }
+    }
+}
+
+struct SelectChild<T: Future> {
+    future: Pin<Box<T>>,
+    woken: Arc<AtomicBool>,
+}
+
+struct SelectWaker {
+    woken: Arc<AtomicBool>,
+    parent: Waker,
+}
+
+impl Wake for SelectWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Release);
+        self.parent.wake_by_ref();
+    }
+}
+
+fn poll_select_child<T: Future>(child: &mut SelectChild<T>, cx: &mut Context<'_>) -> Poll<T::Output> {
+    let waker = Waker::from(Arc::new(SelectWaker {
+        woken: child.woken.clone(),
+        parent: cx.waker().clone(),
+    }));
+    let mut child_cx = Context::from_waker(&waker);
+    child.future.as_mut().poll(&mut child_cx)
+}
+
+// Cheap xorshift64 step; used to randomize poll order so `Select` isn't
+// structurally biased toward `first`.
+fn xorshift(state: &mut u64) -> u64 {
+    let mut x = *state;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    *state = x;
+    x
+}
+
+pub struct Select<T: Future, U: Future> {
+    first: SelectChild<T>,
+    second: SelectChild<U>,
+    first_poll: bool,
+    rng: u64,
 }
 
 #[derive(Debug)]
@@ -374,11 +785,22 @@ where
     U: Future,
 {
     pub fn new(first: T, second: U) -> Self {
+        let first = Box::pin(first);
+        // Cheap, non-cryptographic seed: the freshly-allocated box address is
+        // as good a per-task source of entropy as any here.
+        let seed = (&*first as *const T as usize as u64) | 1;
+
         Self {
-            first: Box::pin(first),
-            second: Box::pin(second),
-            polled_first: false,
-            polled_second: false,
+            first: SelectChild {
+                future: first,
+                woken: Arc::new(AtomicBool::new(true)),
+            },
+            second: SelectChild {
+                future: Box::pin(second),
+                woken: Arc::new(AtomicBool::new(true)),
+            },
+            first_poll: true,
+            rng: seed,
         }
     }
 }
@@ -391,26 +813,44 @@ where
     type Output = Either<T::Output, U::Output>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        // Try first future if not previously completed
-        if !self.polled_first {
-            match self.first.as_mut().poll(cx) {
-                Poll::Ready(result) => return Poll::Ready(Either::First(result)),
-                Poll::Pending => self.polled_first = true,
-            }
-        }
+        let first_poll = mem::replace(&mut self.first_poll, false);
+
+        // Always consume both flags, even on the first poll, so the
+        // constructor's initial `true` doesn't leak into the next poll and
+        // cause both children to be re-polled on the next real wakeup.
+        let first_woken = self.first.woken.swap(false, AcqRel);
+        let second_woken = self.second.woken.swap(false, AcqRel);
+        // On the very first poll both children are driven regardless of
+        // their woken flag; afterwards only re-poll whoever asked for it.
+        let first_ready = first_poll || first_woken;
+        let second_ready = first_poll || second_woken;
 
-        // Try second future if not previously completed
-        if !self.polled_second {
-            match self.second.as_mut().poll(cx) {
-                Poll::Ready(result) => return Poll::Ready(Either::Second(result)),
-                Poll::Pending => self.polled_second = true,
+        let first_goes_first = xorshift(&mut self.rng) & 1 == 0;
+
+        if first_goes_first {
+            if first_ready {
+                if let Poll::Ready(result) = poll_select_child(&mut self.first, cx) {
+                    return Poll::Ready(Either::First(result));
+                }
+            }
+            if second_ready {
+                if let Poll::Ready(result) = poll_select_child(&mut self.second, cx) {
+                    return Poll::Ready(Either::Second(result));
+                }
+            }
+        } else {
+            if second_ready {
+                if let Poll::Ready(result) = poll_select_child(&mut self.second, cx) {
+                    return Poll::Ready(Either::Second(result));
+                }
+            }
+            if first_ready {
+                if let Poll::Ready(result) = poll_select_child(&mut self.first, cx) {
+                    return Poll::Ready(Either::First(result));
+                }
             }
         }
 
-        // Reset poll flags to try again next time
-        self.polled_first = false;
-        self.polled_second = false;
-
         Poll::Pending
     }
 }
@@ -435,3 +875,297 @@ pub trait SelectExt: Future + Sized {
 }
 
 impl<F: Future> SelectExt for F {}
+
+#[cfg(test)]
+mod select_tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.fetch_add(1, SeqCst);
+        }
+    }
+
+    struct NeverReady(Arc<AtomicUsize>);
+
+    impl Future for NeverReady {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            self.0.fetch_add(1, SeqCst);
+            Poll::Pending
+        }
+    }
+
+    // Regression test: `first_ready`/`second_ready` used to short-circuit the
+    // `woken.swap` on the very first poll, leaving both flags at their
+    // constructor-initial `true` uncleared and causing both children to be
+    // needlessly re-polled on poll #2, regardless of which one actually woke
+    // the parent.
+    #[test]
+    fn only_repolls_the_child_that_actually_woke() {
+        let first_polls = Arc::new(AtomicUsize::new(0));
+        let second_polls = Arc::new(AtomicUsize::new(0));
+
+        let mut select = Select::new(
+            NeverReady(first_polls.clone()),
+            NeverReady(second_polls.clone()),
+        );
+
+        let wakes = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(wakes.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut select).poll(&mut cx).is_pending());
+        assert_eq!(first_polls.load(SeqCst), 1, "first poll drives both children");
+        assert_eq!(second_polls.load(SeqCst), 1, "first poll drives both children");
+
+        assert!(Pin::new(&mut select).poll(&mut cx).is_pending());
+        assert_eq!(
+            first_polls.load(SeqCst),
+            1,
+            "neither child signalled a wakeup, so poll #2 must not re-poll either one"
+        );
+        assert_eq!(
+            second_polls.load(SeqCst),
+            1,
+            "neither child signalled a wakeup, so poll #2 must not re-poll either one"
+        );
+    }
+}
+
+struct SelectAllEntry<F> {
+    future: F,
+    woken: Arc<AtomicBool>,
+}
+
+struct SelectAllWaker {
+    woken: Arc<AtomicBool>,
+    parent: Waker,
+}
+
+impl Wake for SelectAllWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Release);
+        self.parent.wake_by_ref();
+    }
+}
+
+/// Drives a collection of futures and resolves as soon as any one of them
+/// does, handing back the index that won and the futures that are still
+/// in flight.
+pub struct SelectAll<F> {
+    entries: Vec<SelectAllEntry<F>>,
+}
+
+impl<F: Future + Unpin> SelectAll<F> {
+    /// # Panics
+    ///
+    /// Panics if `iter` is empty. There's no `F::Output` to produce once
+    /// every entry is gone, so an empty collection can't resolve — without
+    /// this check `poll` would scan zero entries forever and hang with no
+    /// waker ever registered. This matches `futures::select_all`'s contract.
+    pub fn new(iter: impl IntoIterator<Item = F>) -> Self {
+        let entries: Vec<_> = iter
+            .into_iter()
+            .map(|future| SelectAllEntry {
+                future,
+                woken: Arc::new(AtomicBool::new(true)),
+            })
+            .collect();
+
+        assert!(
+            !entries.is_empty(),
+            "select_all() was called with an empty collection of futures"
+        );
+
+        Self { entries }
+    }
+}
+
+impl<F: Future + Unpin> Future for SelectAll<F> {
+    type Output = (F::Output, usize, Vec<F>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let len = self.entries.len();
+
+        for i in 0..len {
+            let entry = &mut self.entries[i];
+            if !entry.woken.swap(false, AcqRel) {
+                continue;
+            }
+
+            let waker = Waker::from(Arc::new(SelectAllWaker {
+                woken: entry.woken.clone(),
+                parent: cx.waker().clone(),
+            }));
+            let mut child_cx = Context::from_waker(&waker);
+
+            if let Poll::Ready(output) = Pin::new(&mut entry.future).poll(&mut child_cx) {
+                let mut entries = mem::take(&mut self.entries);
+                entries.remove(i);
+                let remaining = entries.into_iter().map(|entry| entry.future).collect();
+                return Poll::Ready((output, i, remaining));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+// Utility function to select over a collection of futures
+pub fn select_all<F: Future + Unpin>(iter: impl IntoIterator<Item = F>) -> SelectAll<F> {
+    SelectAll::new(iter)
+}
+
+/// Drives a collection of fallible futures, resolving to the first `Ok` and
+/// the futures still in flight; only fails once every future has errored.
+pub struct SelectOk<F> {
+    inner: SelectAll<F>,
+}
+
+impl<F, T, E> SelectOk<F>
+where
+    F: Future<Output = Result<T, E>> + Unpin,
+{
+    pub fn new(iter: impl IntoIterator<Item = F>) -> Self {
+        Self {
+            inner: SelectAll::new(iter),
+        }
+    }
+}
+
+impl<F, T, E> Future for SelectOk<F>
+where
+    F: Future<Output = Result<T, E>> + Unpin,
+{
+    type Output = Result<(T, Vec<F>), E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match Pin::new(&mut self.inner).poll(cx) {
+                Poll::Ready((Ok(value), _index, remaining)) => {
+                    return Poll::Ready(Ok((value, remaining)));
+                }
+                Poll::Ready((Err(err), _index, remaining)) => {
+                    if remaining.is_empty() {
+                        return Poll::Ready(Err(err));
+                    }
+                    // Keep racing whoever is left until one succeeds or
+                    // everyone has failed.
+                    self.inner = SelectAll::new(remaining);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+// Utility function to select the first Ok out of a collection of futures
+pub fn select_ok<F, T, E>(iter: impl IntoIterator<Item = F>) -> SelectOk<F>
+where
+    F: Future<Output = Result<T, E>> + Unpin,
+{
+    SelectOk::new(iter)
+}
+
+#[cfg(test)]
+mod select_all_tests {
+    use super::*;
+
+    struct Immediate(usize);
+
+    impl Future for Immediate {
+        type Output = usize;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<usize> {
+            Poll::Ready(self.0)
+        }
+    }
+
+    struct Gated {
+        value: usize,
+        gate: Arc<AtomicBool>,
+    }
+
+    impl Future for Gated {
+        type Output = usize;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<usize> {
+            if self.gate.load(SeqCst) {
+                Poll::Ready(self.value)
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn resolves_with_the_first_ready_future_and_the_rest_still_in_flight() {
+        let mut select_all = SelectAll::new(vec![
+            Box::pin(Gated {
+                value: 0,
+                gate: Arc::new(AtomicBool::new(false)),
+            }) as Pin<Box<dyn Future<Output = usize>>>,
+            Box::pin(Gated {
+                value: 1,
+                gate: Arc::new(AtomicBool::new(false)),
+            }),
+            Box::pin(Immediate(2)) as Pin<Box<dyn Future<Output = usize>>>,
+        ]);
+
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        match Pin::new(&mut select_all).poll(&mut cx) {
+            Poll::Ready((output, index, remaining)) => {
+                assert_eq!(output, 2);
+                assert_eq!(index, 2, "Immediate is the third entry and resolves first");
+                assert_eq!(remaining.len(), 2, "the two still-pending entries come back");
+            }
+            Poll::Pending => panic!("Immediate should have resolved on the first poll"),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "empty collection")]
+    fn select_all_panics_on_an_empty_collection() {
+        let _: SelectAll<Pin<Box<dyn Future<Output = ()>>>> = SelectAll::new(Vec::new());
+    }
+
+    #[test]
+    fn select_ok_resolves_to_the_first_ok() {
+        let mut select_ok = SelectOk::new(vec![
+            Box::pin(async { Err::<usize, &str>("nope") }) as Pin<Box<dyn Future<Output = Result<usize, &str>>>>,
+            Box::pin(async { Ok::<usize, &str>(7) }),
+        ]);
+
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        match Pin::new(&mut select_ok).poll(&mut cx) {
+            Poll::Ready(Ok((value, _remaining))) => assert_eq!(value, 7),
+            Poll::Ready(Err(_)) => panic!("expected Ok(7), got Err"),
+            Poll::Pending => panic!("expected Ok(7), got Pending"),
+        }
+    }
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+        fn wake_by_ref(self: &Arc<Self>) {}
+    }
+}
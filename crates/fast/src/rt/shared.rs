@@ -0,0 +1,270 @@
+use std::{
+    cell::UnsafeCell,
+    mem,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU8, Ordering::*},
+    },
+    task::{Context, Poll, Waker},
+};
+
+use slab::Slab;
+
+enum FutureOrOutput<F: Future> {
+    Future(F),
+    Output(F::Output),
+    Taken,
+}
+
+struct Inner<F: Future> {
+    future_or_output: UnsafeCell<FutureOrOutput<F>>,
+    state: AtomicU8,
+    wakers: Mutex<Slab<Option<Waker>>>,
+}
+
+// SAFETY: until `COMPLETE`, access to `future_or_output` is gated by the
+// `IDLE -> POLLING` CAS on `state`, so only one clone touches it at a time.
+// Once `COMPLETE`, every clone reads the stored output through a shared
+// reference concurrently, hence `F::Output: Sync` for `Sync`.
+unsafe impl<F: Future + Send> Send for Inner<F> where F::Output: Send {}
+unsafe impl<F: Future + Send> Sync for Inner<F> where F::Output: Send + Sync {}
+
+impl<F: Future> Inner<F> {
+    const IDLE: u8 = 0;
+    const POLLING: u8 = 1;
+    const COMPLETE: u8 = 2;
+    const POISONED: u8 = 3;
+
+    fn drain_wakers(&self) {
+        let mut wakers = self.wakers.lock().unwrap();
+        for (_, waker) in wakers.iter_mut() {
+            if let Some(waker) = waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+// Marks the inner future poisoned if it panics, so a later poll can't read
+// the half-clobbered `future_or_output` cell.
+struct PoisonOnUnwind<'a, F: Future> {
+    inner: &'a Inner<F>,
+    completed: bool,
+}
+
+impl<'a, F: Future> Drop for PoisonOnUnwind<'a, F> {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.inner.state.store(Inner::<F>::POISONED, Release);
+            self.inner.drain_wakers();
+        }
+    }
+}
+
+/// A cloneable future that drives its inner future to completion once and
+/// fans the output out to every clone.
+///
+/// Only one clone drives the inner future at a time; the rest register a
+/// waker and are woken together once it resolves.
+pub struct Shared<F: Future> {
+    inner: Arc<Inner<F>>,
+    waker_key: usize,
+}
+
+impl<F: Future> Shared<F> {
+    const NO_WAKER: usize = usize::MAX;
+
+    pub fn new(future: F) -> Self {
+        let inner = Arc::new(Inner {
+            future_or_output: UnsafeCell::new(FutureOrOutput::Future(future)),
+            state: AtomicU8::new(Inner::<F>::IDLE),
+            wakers: Mutex::new(Slab::new()),
+        });
+
+        Self {
+            inner,
+            waker_key: Self::NO_WAKER,
+        }
+    }
+
+    fn register_waker(&mut self, waker: &Waker) {
+        let mut wakers = self.inner.wakers.lock().unwrap();
+        if self.waker_key == Self::NO_WAKER {
+            self.waker_key = wakers.insert(Some(waker.clone()));
+        } else {
+            wakers[self.waker_key] = Some(waker.clone());
+        }
+    }
+}
+
+impl<F: Future> Shared<F>
+where
+    F::Output: Clone,
+{
+    fn clone_output(&self) -> F::Output {
+        match unsafe { &*self.inner.future_or_output.get() } {
+            FutureOrOutput::Output(output) => output.clone(),
+            _ => unreachable!("COMPLETE state implies a stored output"),
+        }
+    }
+}
+
+impl<F: Future> Future for Shared<F>
+where
+    F::Output: Clone,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.inner.state.load(Acquire) {
+            Inner::<F>::COMPLETE => return Poll::Ready(self.clone_output()),
+            Inner::<F>::POISONED => panic!("polled a Shared future that previously panicked"),
+            _ => {}
+        }
+
+        self.register_waker(cx.waker());
+
+        match self.inner.state.compare_exchange(
+            Inner::<F>::IDLE,
+            Inner::<F>::POLLING,
+            AcqRel,
+            Acquire,
+        ) {
+            Err(Inner::<F>::COMPLETE) => return Poll::Ready(self.clone_output()),
+            Err(_) => return Poll::Pending,
+            Ok(_) => {}
+        }
+
+        let mut guard = PoisonOnUnwind {
+            inner: &self.inner,
+            completed: false,
+        };
+
+        let poll = {
+            let future_or_output = unsafe { &mut *self.inner.future_or_output.get() };
+            match future_or_output {
+                FutureOrOutput::Future(fut) => unsafe { Pin::new_unchecked(fut) }.poll(cx),
+                FutureOrOutput::Output(_) | FutureOrOutput::Taken => {
+                    unreachable!("only the current poller observes the future slot")
+                }
+            }
+        };
+
+        // Poll returned without panicking; disarm the poison guard.
+        guard.completed = true;
+        mem::forget(guard);
+
+        match poll {
+            Poll::Ready(output) => {
+                let future_or_output = unsafe { &mut *self.inner.future_or_output.get() };
+                *future_or_output = FutureOrOutput::Output(output.clone());
+                self.inner.state.store(Inner::<F>::COMPLETE, Release);
+                self.inner.drain_wakers();
+                Poll::Ready(output)
+            }
+            Poll::Pending => {
+                self.inner.state.store(Inner::<F>::IDLE, Release);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<F: Future> Clone for Shared<F> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            waker_key: Self::NO_WAKER,
+        }
+    }
+}
+
+impl<F: Future> Drop for Shared<F> {
+    fn drop(&mut self) {
+        if self.waker_key != Self::NO_WAKER {
+            let mut wakers = self.inner.wakers.lock().unwrap();
+            if wakers.contains(self.waker_key) {
+                wakers.remove(self.waker_key);
+            }
+        }
+    }
+}
+
+// Extension trait for more ergonomic usage
+pub trait FutureExt: Future + Sized {
+    fn shared(self) -> Shared<Self> {
+        Shared::new(self)
+    }
+}
+
+impl<F: Future> FutureExt for F {}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, AtomicUsize},
+        task::Wake,
+    };
+
+    use super::*;
+
+    struct CountingWaker(Arc<AtomicUsize>);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.fetch_add(1, SeqCst);
+        }
+    }
+
+    struct Gated {
+        gate: Arc<AtomicBool>,
+    }
+
+    impl Future for Gated {
+        type Output = i32;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<i32> {
+            if self.gate.load(SeqCst) {
+                Poll::Ready(42)
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn fans_out_output_to_every_clone() {
+        let gate = Arc::new(AtomicBool::new(false));
+        let shared = Shared::new(Gated { gate: gate.clone() });
+        let mut a = shared.clone();
+        let mut b = shared.clone();
+
+        let b_wakes = Arc::new(AtomicUsize::new(0));
+        let waker_b = Waker::from(Arc::new(CountingWaker(b_wakes.clone())));
+        let mut cx_b = Context::from_waker(&waker_b);
+        assert_eq!(Pin::new(&mut b).poll(&mut cx_b), Poll::Pending);
+
+        let waker_a = Waker::from(Arc::new(CountingWaker(Arc::new(AtomicUsize::new(0)))));
+        let mut cx_a = Context::from_waker(&waker_a);
+
+        gate.store(true, SeqCst);
+        assert_eq!(Pin::new(&mut a).poll(&mut cx_a), Poll::Ready(42));
+        assert_eq!(
+            b_wakes.load(SeqCst),
+            1,
+            "completing the shared future should wake every other registered clone"
+        );
+
+        // `b` resolves from the cached output without re-polling the inner future.
+        assert_eq!(Pin::new(&mut b).poll(&mut cx_b), Poll::Ready(42));
+
+        // A clone taken after completion should also observe the cached output.
+        let mut c = shared.clone();
+        assert_eq!(Pin::new(&mut c).poll(&mut cx_a), Poll::Ready(42));
+    }
+}
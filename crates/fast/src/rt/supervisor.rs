@@ -0,0 +1,317 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use super::{
+    Act, BoxSendFut, Key, Kind, Task,
+    abort::{AbortHandle, AbortRegistration, Aborted, next_key},
+    block_on,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// What to restart when a supervised task's future resolves to a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Only the failed task is restarted.
+    OneForOne,
+    /// The failed task and all of its siblings are restarted.
+    OneForAll,
+}
+
+/// Read-only snapshot of one node in a `Supervisor`'s tree, for an external
+/// tracing/console layer to observe live task topology.
+#[derive(Debug, Clone)]
+pub struct TaskNode<K: Kind + Eq + Hash + Send + 'static> {
+    pub key: Key<K>,
+    pub parent: Option<Key<K>>,
+    pub state: TaskState,
+}
+
+type ActFactory = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<(), ()>> + Send>> + Send + Sync>;
+
+struct Node<K: Kind + Eq + Hash + Send + 'static> {
+    parent: Option<Key<K>>,
+    children: Vec<Key<K>>,
+    state: TaskState,
+    abort: AbortHandle,
+    restart: RestartPolicy,
+    act: ActFactory,
+}
+
+/// A tree of spawned tasks: cancelling a group aborts every descendant
+/// before the parent, and a supervised task's restart policy is evaluated
+/// when its future resolves to a failure.
+pub struct Supervisor<K: Kind + Eq + Hash + Send + BoxSendFut + 'static> {
+    nodes: Mutex<HashMap<Key<K>, Node<K>>>,
+}
+
+impl<K: Kind + Eq + Hash + Send + BoxSendFut + 'static> Supervisor<K> {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            nodes: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Spawn `act` under `parent` (or as a root if `None`), linking it into
+    /// the supervision tree and scheduling it through `K::schedule`.
+    pub fn spawn_supervised<G, F>(
+        self: &Arc<Self>,
+        parent: Option<Key<K>>,
+        restart: RestartPolicy,
+        act: G,
+    ) -> Key<K>
+    where
+        G: Fn() -> F + Send + Sync + 'static,
+        F: Future<Output = Result<(), ()>> + Send + 'static,
+    {
+        let act: ActFactory = Arc::new(move || -> Pin<Box<dyn Future<Output = Result<(), ()>> + Send>> {
+            Box::pin(act())
+        });
+        self.spawn_act(parent, restart, act)
+    }
+
+    /// Walk the subtree rooted at `key` depth-first and abort every
+    /// descendant before the node itself, so cancelling a group can't leave
+    /// orphaned work enqueued.
+    pub fn shutdown(&self, key: Key<K>) {
+        let children = {
+            let nodes = self.nodes.lock().unwrap();
+            nodes.get(&key).map(|node| node.children.clone())
+        };
+
+        for child in children.into_iter().flatten() {
+            self.shutdown(child);
+        }
+
+        let mut nodes = self.nodes.lock().unwrap();
+        if let Some(node) = nodes.get_mut(&key) {
+            node.abort.abort();
+            node.state = TaskState::Cancelled;
+        }
+    }
+
+    /// A read-only snapshot of the current tree.
+    pub fn tree(&self) -> Vec<TaskNode<K>> {
+        self.nodes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, node)| TaskNode {
+                key: *key,
+                parent: node.parent,
+                state: node.state,
+            })
+            .collect()
+    }
+
+    fn spawn_act(self: &Arc<Self>, parent: Option<Key<K>>, restart: RestartPolicy, act: ActFactory) -> Key<K> {
+        let key = next_key::<K>();
+        let (handle, registration) = AbortHandle::new_pair();
+
+        {
+            let mut nodes = self.nodes.lock().unwrap();
+            nodes.insert(
+                key,
+                Node {
+                    parent,
+                    children: Vec::new(),
+                    state: TaskState::Running,
+                    abort: handle,
+                    restart,
+                    act: act.clone(),
+                },
+            );
+            if let Some(parent) = parent {
+                if let Some(parent_node) = nodes.get_mut(&parent) {
+                    parent_node.children.push(key);
+                }
+            }
+        }
+
+        self.schedule(key, registration, act);
+        key
+    }
+
+    fn schedule(self: &Arc<Self>, key: Key<K>, registration: AbortRegistration, act: ActFactory) {
+        let supervisor = self.clone();
+        let body = super::abort::Abortable::new(act(), registration);
+
+        let task_act = Act::Fut(K::boxed(async move {
+            let outcome = body.await;
+            supervisor.on_finish(key, outcome);
+        }));
+
+        let task = Task {
+            key,
+            act: Some(task_act),
+        };
+        block_on(K::schedule(task));
+    }
+
+    fn on_finish(self: &Arc<Self>, key: Key<K>, outcome: Result<Result<(), ()>, Aborted>) {
+        let failed = matches!(outcome, Ok(Err(())));
+
+        let Some((restart, parent, act)) = ({
+            let mut nodes = self.nodes.lock().unwrap();
+            nodes.get_mut(&key).map(|node| {
+                node.state = match outcome {
+                    Ok(Ok(())) => TaskState::Completed,
+                    Ok(Err(())) => TaskState::Failed,
+                    Err(_) => TaskState::Cancelled,
+                };
+                (node.restart, node.parent, node.act.clone())
+            })
+        }) else {
+            return;
+        };
+
+        if !failed {
+            return;
+        }
+
+        match restart {
+            RestartPolicy::OneForOne => self.restart_one(key, parent, restart, act),
+            RestartPolicy::OneForAll => {
+                for (sibling_key, sibling_restart, sibling_act) in self.siblings_including(key, parent) {
+                    self.restart_one(sibling_key, parent, sibling_restart, sibling_act);
+                }
+            }
+        }
+    }
+
+    /// Siblings of `key` (or `key` itself, if its parent has already been
+    /// removed), each with its own `restart` policy — a `OneForAll` cascade
+    /// must not silently promote a `OneForOne` sibling's policy to match the
+    /// task that triggered the restart.
+    fn siblings_including(&self, key: Key<K>, parent: Option<Key<K>>) -> Vec<(Key<K>, RestartPolicy, ActFactory)> {
+        let nodes = self.nodes.lock().unwrap();
+        let keys: Vec<Key<K>> = match parent {
+            Some(parent) => nodes
+                .get(&parent)
+                .map(|node| node.children.clone())
+                .unwrap_or_else(|| vec![key]),
+            None => nodes
+                .iter()
+                .filter(|(_, node)| node.parent.is_none())
+                .map(|(key, _)| *key)
+                .collect(),
+        };
+
+        keys.into_iter()
+            .filter_map(|key| nodes.get(&key).map(|node| (key, node.restart, node.act.clone())))
+            .collect()
+    }
+
+    fn restart_one(
+        self: &Arc<Self>,
+        key: Key<K>,
+        parent: Option<Key<K>>,
+        restart: RestartPolicy,
+        act: ActFactory,
+    ) {
+        {
+            let mut nodes = self.nodes.lock().unwrap();
+            // The node being restarted may still be `Running` (e.g. a
+            // `OneForAll` sibling of the task that actually failed), so its
+            // old instance must be aborted before we drop its `Node` and
+            // spawn a replacement — otherwise the old instance keeps running
+            // untracked alongside the new one.
+            if let Some(node) = nodes.remove(&key) {
+                node.abort.abort();
+            }
+            if let Some(parent) = parent {
+                if let Some(parent_node) = nodes.get_mut(&parent) {
+                    parent_node.children.retain(|child| *child != key);
+                }
+            }
+        }
+
+        self.spawn_act(parent, restart, act);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        task::Waker,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct TestKind;
+
+    impl Kind for TestKind {
+        type Call = Box<dyn FnOnce()>;
+        type Fut = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+        fn waker(_id: Key<Self>) -> Waker {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn schedule(task: Task<Self>) {
+            if let Some(Act::Fut(fut)) = task.act {
+                fut.await;
+            }
+        }
+    }
+
+    impl BoxSendFut for TestKind {
+        fn boxed(fut: impl Future<Output = ()> + Send + 'static) -> Self::Fut {
+            Box::pin(fut)
+        }
+    }
+
+    // Regression test: a `OneForAll` restart used to drop a still-alive
+    // sibling's `Node` (and its `AbortHandle`) without ever aborting it,
+    // leaving the old instance running untracked alongside its replacement.
+    #[test]
+    fn one_for_all_aborts_a_sibling_before_restarting_it() {
+        let supervisor = Supervisor::<TestKind>::new();
+
+        let succeeding_key =
+            supervisor.spawn_supervised(None, RestartPolicy::OneForAll, || async { Ok(()) });
+
+        let original_handle = supervisor
+            .nodes
+            .lock()
+            .unwrap()
+            .get(&succeeding_key)
+            .unwrap()
+            .abort
+            .clone();
+
+        let fail_once = Arc::new(AtomicBool::new(true));
+        supervisor.spawn_supervised(None, RestartPolicy::OneForAll, move || {
+            let fail_once = fail_once.clone();
+            async move {
+                if fail_once.swap(false, Ordering::SeqCst) {
+                    Err(())
+                } else {
+                    Ok(())
+                }
+            }
+        });
+
+        assert!(
+            original_handle.is_aborted(),
+            "a OneForAll restart must abort a sibling's old instance before respawning it"
+        );
+        assert_eq!(
+            supervisor.nodes.lock().unwrap().len(),
+            2,
+            "both siblings should have been respawned under fresh keys"
+        );
+    }
+}